@@ -1,13 +1,37 @@
+mod ai;
+mod animation;
+mod debug_overlay;
+mod genetic;
+mod hud;
+mod scenes;
+mod waves;
+
+use ai::{AiMovement, BasicAi, DiverAi, AI};
+use animation::SpriteAnimation;
+use debug_overlay::DebugOverlay;
+use genetic::Genome;
+use hud::{ControlBar, ControlBarButton, Hud, Playback};
+use scenes::{GameOverScene, PauseScene, Scene, SceneManager, SceneTransition, TitleScene};
+use waves::WaveConfig;
 use quicksilver::{
-    geom::{Rectangle, Scalar, Shape, Vector},
-    graphics::{Color, Graphics, Image},
+    geom::{Rectangle, Shape, Vector},
+    graphics::{Color, FontRenderer, Graphics, Image, VectorFont},
     lifecycle::event::KeyboardEvent,
     lifecycle::{run, Event, EventStream, Key, Settings, Window},
     Result,
 };
 use space_invaders::utils::timer::Timer;
 use std::borrow::Borrow;
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Loaded once at startup and shared (via `Rc<RefCell<_>>`, since a
+/// `FontRenderer` caches glyph textures and isn't `Clone`) by every scene and
+/// HUD element that draws text. Passed around as `Option<SharedFont>`, which
+/// is `None` if `font.ttf` failed to load, in which case text elements simply
+/// don't draw instead of panicking at startup.
+pub type SharedFont = Rc<RefCell<FontRenderer>>;
 
 fn main() {
     run(
@@ -24,34 +48,76 @@ async fn app(window: Window, mut gfx: Graphics, mut events: EventStream) -> Resu
     // Clear the screen to a blank, white color
     gfx.clear(Color::WHITE);
 
-    let mut game = Game::new();
-    game.init(&mut gfx).await;
+    let images = load_images(&gfx).await;
+    let wave_config = WaveConfig::load("waves.json").await;
+    let font = load_font(&gfx).await;
+    let mut scenes = SceneManager::new(Box::new(TitleScene::new(images, wave_config, font)));
 
     let mut update_timer = Timer::time_per_second(30.0);
     let mut draw_timer = Timer::time_per_second(60.0);
+    let mut mouse_pos = Vector::ZERO;
 
     loop {
         while let Some(event) = events.next_event().await {
             match event {
-                Event::KeyboardInput(event) => game.handle_key(event),
+                Event::KeyboardInput(event) => scenes.handle_key(event),
+                Event::PointerMoved(event) => mouse_pos = event.location(),
+                Event::PointerInput(event) => {
+                    if event.is_down() {
+                        scenes.handle_click(mouse_pos);
+                    }
+                }
                 _ => (),
             }
         }
 
         // We use a while loop rather than an if so that we can try to catch up in the event of having a slow down.
         while update_timer.tick() {
-            game.update()
+            scenes.update()
         }
 
         if draw_timer.exhaust().is_some() {
             gfx.clear(Color::BLACK);
 
-            game.render(&mut gfx);
+            scenes.render(&mut gfx);
             gfx.present(&window)?;
         }
     }
 }
 
+async fn load_images(gfx: &Graphics) -> HashMap<String, Box<Image>> {
+    let mut images: HashMap<String, Box<Image>> = HashMap::new();
+    images.insert(
+        String::from("player"),
+        Box::new(Image::load(&gfx, "player.png").await.unwrap()),
+    );
+    images.insert(
+        String::from("enemy"),
+        Box::new(Image::load(&gfx, "enemy.png").await.unwrap()),
+    );
+    // Unlike the player/enemy sprites above, explosion frames are a cosmetic
+    // extra: a missing frame just drops out of `explosion_frames` below
+    // instead of taking down startup.
+    for i in 0..EXPLOSION_FRAME_COUNT {
+        if let Ok(image) = Image::load(&gfx, &format!("explosion_{}.png", i)).await {
+            images.insert(format!("explosion_{}", i), Box::new(image));
+        }
+    }
+    images
+}
+
+/// Loads the HUD/overlay text font, degrading to `None` (text elements just
+/// don't draw) instead of panicking if `font.ttf` is missing or unusable,
+/// matching `WaveConfig::load`'s graceful fallback for a missing asset.
+async fn load_font(gfx: &Graphics) -> Option<SharedFont> {
+    let ttf = VectorFont::load("font.ttf").await.ok()?;
+    let renderer = ttf.to_renderer(gfx, 16.0).ok()?;
+    Some(Rc::new(RefCell::new(renderer)))
+}
+
+/// Number of frames in the enemy death explosion animation.
+const EXPLOSION_FRAME_COUNT: usize = 4;
+
 #[derive(Eq, PartialEq)]
 enum Movement {
     None,
@@ -61,61 +127,188 @@ enum Movement {
 
 pub struct Game {
     player: Entity,
-    enemies: Vec<Entity>,
+    enemies: Vec<Enemy>,
     bullets: Vec<Entity>,
+    explosions: Vec<Entity>,
+    explosion_frames: Vec<Box<Image>>,
     player_movement: Movement,
     images: HashMap<String, Box<Image>>,
+    wave_config: WaveConfig,
+    current_wave: usize,
+    wave_delay_ticks: Option<u32>,
+    player_speed: f32,
+    enemy_move_amount: f32,
+    bullet_speed: f32,
+    extra_spawn_count: u32,
+    debug_overlay: DebugOverlay,
+    bullets_evaded: u32,
+    trained_genome: Option<Genome>,
+    hud: Hud,
+    control_bar: ControlBar,
+    playback: Playback,
+    score: u32,
+    lives: u32,
+    font: Option<SharedFont>,
 }
 
 impl Game {
-    pub fn new() -> Self {
-        let images: HashMap<String, Box<Image>> = HashMap::new();
+    pub fn new(images: HashMap<String, Box<Image>>, wave_config: WaveConfig, font: Option<SharedFont>) -> Self {
+        let player = Entity::new_player(
+            400.0,
+            550.0,
+            EntityView::Image(images.get("player").unwrap().clone()),
+        );
 
-        Self {
-            player: Entity::new_player(400.0, 550.0, EntityView::None),
+        let explosion_frames = (0..EXPLOSION_FRAME_COUNT)
+            .filter_map(|i| images.get(&format!("explosion_{}", i)).cloned())
+            .collect();
+
+        let mut game = Self {
+            player,
             enemies: vec![],
             bullets: vec![],
+            explosions: vec![],
+            explosion_frames,
             player_movement: Movement::None,
             images,
+            wave_config,
+            current_wave: 0,
+            wave_delay_ticks: None,
+            player_speed: 10.0,
+            enemy_move_amount: 1.0,
+            bullet_speed: 20.0,
+            extra_spawn_count: 0,
+            debug_overlay: DebugOverlay::new(),
+            bullets_evaded: 0,
+            trained_genome: genetic::load_best(genetic::GENOME_SAVE_PATH),
+            hud: Hud,
+            control_bar: ControlBar::new(),
+            playback: Playback::Normal,
+            score: 0,
+            lives: 3,
+            font,
+        };
+        game.spawn_wave(0);
+        game
+    }
+
+    fn spawn_wave(&mut self, wave_index: usize) {
+        let wave = match self.wave_config.waves.get(wave_index) {
+            Some(wave) => wave.clone(),
+            None => return,
+        };
+
+        for spawn in &wave.enemies {
+            let image = self
+                .images
+                .get(&spawn.sprite)
+                .unwrap_or_else(|| panic!("unknown sprite key in wave config: {}", spawn.sprite));
+
+            // `extra_spawn_count` (tunable via the debug overlay) spawns
+            // additional copies of this config entry side by side, so wave
+            // density can be tested without editing waves.json.
+            for copy in 0..=self.extra_spawn_count {
+                let ai: Box<dyn AI> = match &self.trained_genome {
+                    Some(genome) => Box::new(genetic::GeneticAi::new(genome.clone())),
+                    None if spawn.pattern == 1 => Box::new(DiverAi::new()),
+                    None => Box::new(BasicAi::new()),
+                };
+
+                let x = spawn.x + copy as f32 * 35.0;
+                self.enemies.push(Enemy::new(
+                    Entity::new_enemy(x, spawn.y, EntityView::Image(image.clone())),
+                    ai,
+                    spawn.hp,
+                ));
+            }
         }
     }
-    pub async fn init(&mut self, gfx: &mut Graphics) {
-        self.images.insert(
-            String::from("player"),
-            Box::new(Image::load(&gfx, "player.png").await.unwrap()),
-        );
-        self.images.insert(
-            String::from("enemy"),
-            Box::new(Image::load(&gfx, "enemy.png").await.unwrap()),
-        );
 
-        self.player.set_view(EntityView::Image(
-            self.images.get("player").unwrap().clone(),
+    fn spawn_explosion(&mut self, position: Vector) {
+        // No frames loaded (see `load_images`'s graceful fallback) means no
+        // explosion animation to play; just skip it rather than panicking.
+        if self.explosion_frames.is_empty() {
+            return;
+        }
+        let animation = SpriteAnimation::new(self.explosion_frames.clone(), 4, false);
+        self.explosions.push(Entity::new(
+            position.x,
+            position.y,
+            30.0,
+            30.0,
+            EntityView::Animation(animation),
         ));
-        for i in 1..10 {
-            self.enemies.push(Entity::new_enemy(
-                150.0 + i.float() * 50.0,
-                20.0,
-                EntityView::Image(self.images.get("enemy").unwrap().clone()),
-            ))
+    }
+
+    /// Waits out the configured inter-wave delay, then spawns the next wave.
+    /// A no-op once the last configured wave has been cleared.
+    fn advance_wave(&mut self) {
+        let next_wave = self.current_wave + 1;
+        if next_wave >= self.wave_config.waves.len() {
+            return;
+        }
+
+        match self.wave_delay_ticks {
+            Some(0) => {
+                self.spawn_wave(next_wave);
+                self.current_wave = next_wave;
+                self.wave_delay_ticks = None;
+            }
+            Some(remaining) => self.wave_delay_ticks = Some(remaining - 1),
+            None => {
+                self.wave_delay_ticks =
+                    Some((self.wave_config.waves[self.current_wave].delay * 30.0) as u32)
+            }
         }
     }
 
     pub fn update(&mut self) {
         match self.player_movement {
-            Movement::Left => self.player.move_left(10.0),
-            Movement::Right => self.player.move_right(10.0),
+            Movement::Left => self.player.move_left(self.player_speed),
+            Movement::Right => self.player.move_right(self.player_speed),
             _ => (),
         }
 
-        for enemy in &mut self.enemies.iter_mut() {
-            enemy.move_down(1.0);
+        let enemy_move_amount = self.enemy_move_amount;
+        for i in 0..self.enemies.len() {
+            let mut ai = self.enemies[i].ai.take().expect("enemy without an AI");
+            let position = self.enemies[i].entity.center();
+            let goal = ai.plan(self, position);
+            let movement = ai.step(&goal);
+            self.enemies[i].apply_movement(movement, enemy_move_amount);
+            self.enemies[i].ai = Some(ai);
         }
 
+        let mut newly_dead = vec![];
         for bullet in &mut self.bullets.iter_mut() {
-            bullet.move_up(20.0);
+            bullet.move_up(self.bullet_speed);
+
+            for enemy in &mut self.enemies {
+                if enemy.hp > 0 && bullet.hit_test(&enemy.entity) {
+                    enemy.hp = enemy.hp.saturating_sub(1);
+                    if enemy.hp == 0 {
+                        newly_dead.push(enemy.entity.center());
+                        self.score += 10;
+                    }
+                }
+            }
+        }
+        self.enemies.retain(|enemy| enemy.hp > 0);
 
-            self.enemies.retain(|enemy| !bullet.hit_test(enemy))
+        for position in newly_dead {
+            self.spawn_explosion(position);
+        }
+        for explosion in &mut self.explosions {
+            explosion.tick_animation();
+        }
+        self.explosions.retain(|explosion| !explosion.animation_finished());
+
+        let bullets_before = self.bullets.len();
+        self.bullets.retain(|bullet| bullet.center().y > -20.0);
+        self.bullets_evaded += (bullets_before - self.bullets.len()) as u32;
+
+        if self.enemies.is_empty() {
+            self.advance_wave();
         }
     }
 
@@ -140,6 +333,35 @@ impl Game {
                 self.bullets
                     .push(Entity::new_bullet(x, y, EntityView::Color(Color::GREEN)))
             }
+            Key::F1 => {
+                if event.is_down() {
+                    self.debug_overlay.toggle()
+                }
+            }
+            Key::Equals if event.is_down() && self.debug_overlay.is_enabled() => {
+                self.player_speed += 1.0
+            }
+            Key::Minus if event.is_down() && self.debug_overlay.is_enabled() => {
+                self.player_speed = (self.player_speed - 1.0).max(0.0)
+            }
+            Key::RBracket if event.is_down() && self.debug_overlay.is_enabled() => {
+                self.enemy_move_amount += 0.1
+            }
+            Key::LBracket if event.is_down() && self.debug_overlay.is_enabled() => {
+                self.enemy_move_amount = (self.enemy_move_amount - 0.1).max(0.0)
+            }
+            Key::Period if event.is_down() && self.debug_overlay.is_enabled() => {
+                self.bullet_speed += 1.0
+            }
+            Key::Comma if event.is_down() && self.debug_overlay.is_enabled() => {
+                self.bullet_speed = (self.bullet_speed - 1.0).max(0.0)
+            }
+            Key::Slash if event.is_down() && self.debug_overlay.is_enabled() => {
+                self.extra_spawn_count += 1
+            }
+            Key::Backslash if event.is_down() && self.debug_overlay.is_enabled() => {
+                self.extra_spawn_count = self.extra_spawn_count.saturating_sub(1)
+            }
             _ => (),
         }
     }
@@ -154,6 +376,128 @@ impl Renderable for Game {
         for bullet in self.bullets.iter() {
             bullet.render(gfx)
         }
+        for explosion in self.explosions.iter() {
+            explosion.render(gfx)
+        }
+    }
+}
+
+/// Enemy y-coordinate beyond which the invaders have reached the player's line.
+const ENEMY_BREACH_LINE: f32 = 550.0;
+
+impl Scene for Game {
+    fn update(&mut self) -> SceneTransition {
+        let ticks = match self.playback {
+            Playback::Paused => 0,
+            Playback::Normal => 1,
+            Playback::FastForward => 4,
+        };
+        for _ in 0..ticks {
+            self.update();
+        }
+
+        let enemies_breached = self
+            .enemies
+            .iter()
+            .any(|enemy| enemy.entity.center().y > ENEMY_BREACH_LINE);
+
+        if enemies_breached {
+            self.lives = self.lives.saturating_sub(1);
+            if self.lives == 0 {
+                return SceneTransition::Push(Box::new(GameOverScene::new(
+                    self.images.clone(),
+                    self.wave_config.clone(),
+                    self.font.clone(),
+                )));
+            }
+
+            self.enemies.clear();
+            self.spawn_wave(self.current_wave);
+            return SceneTransition::None;
+        }
+
+        let no_more_waves = self.current_wave + 1 >= self.wave_config.waves.len();
+        let enemies_cleared = self.enemies.is_empty() && no_more_waves;
+
+        if enemies_cleared {
+            SceneTransition::Push(Box::new(GameOverScene::new(
+                self.images.clone(),
+                self.wave_config.clone(),
+                self.font.clone(),
+            )))
+        } else {
+            SceneTransition::None
+        }
+    }
+
+    fn render(&self, gfx: &mut Graphics) {
+        Renderable::render(self, gfx);
+        let mut font = self.font.as_ref().map(|font| font.borrow_mut());
+        self.debug_overlay.render(self, gfx, font.as_deref_mut());
+        self.hud.render(gfx, font.as_deref_mut(), self.score, self.lives);
+        self.control_bar.render(gfx);
+    }
+
+    fn handle_key(&mut self, event: KeyboardEvent) -> SceneTransition {
+        if event.is_down() && event.key() == Key::Escape {
+            return SceneTransition::Push(Box::new(PauseScene));
+        }
+
+        self.handle_key(event);
+        SceneTransition::None
+    }
+
+    fn handle_click(&mut self, position: Vector) -> SceneTransition {
+        match self.control_bar.hit_test(position) {
+            Some(ControlBarButton::Pause) => self.playback = Playback::Paused,
+            Some(ControlBarButton::Play) => self.playback = Playback::Normal,
+            Some(ControlBarButton::FastForward) => self.playback = Playback::FastForward,
+            Some(ControlBarButton::Restart) => {
+                return SceneTransition::Replace(Box::new(Game::new(
+                    self.images.clone(),
+                    self.wave_config.clone(),
+                    self.font.clone(),
+                )))
+            }
+            None => (),
+        }
+        SceneTransition::None
+    }
+}
+
+pub struct Enemy {
+    entity: Entity,
+    ai: Option<Box<dyn AI>>,
+    hp: u32,
+}
+
+impl Enemy {
+    pub fn new(entity: Entity, ai: Box<dyn AI>, hp: u32) -> Self {
+        Self {
+            entity,
+            ai: Some(ai),
+            hp,
+        }
+    }
+
+    fn apply_movement(&mut self, movement: AiMovement, amount: f32) {
+        match movement {
+            AiMovement::Idle => (),
+            AiMovement::Left => self.entity.move_left(amount),
+            AiMovement::Right => self.entity.move_right(amount),
+            AiMovement::Up => self.entity.move_up(amount),
+            AiMovement::Down => self.entity.move_down(amount),
+        }
+    }
+
+    pub fn bounds(&self) -> Rectangle {
+        self.entity.bounds()
+    }
+}
+
+impl Renderable for Enemy {
+    fn render(&self, gfx: &mut Graphics) {
+        self.entity.render(gfx)
     }
 }
 
@@ -162,6 +506,7 @@ pub enum EntityView {
     None,
     Image(Box<Image>),
     Color(Color),
+    Animation(SpriteAnimation),
 }
 
 pub struct Entity {
@@ -222,8 +567,23 @@ impl Entity {
         Vector::new(x + w / 2.0, y + h / 2.0)
     }
 
-    pub fn set_view(&mut self, view: EntityView) {
-        self.view = view
+    pub fn bounds(&self) -> Rectangle {
+        self.rect
+    }
+
+    /// Advances this entity's view by one logic tick, if it is animated.
+    pub fn tick_animation(&mut self) {
+        if let EntityView::Animation(animation) = &mut self.view {
+            animation.tick();
+        }
+    }
+
+    /// `true` once a non-looping animation has played its last frame.
+    pub fn animation_finished(&self) -> bool {
+        match &self.view {
+            EntityView::Animation(animation) => animation.is_finished(),
+            _ => false,
+        }
     }
 }
 
@@ -236,6 +596,10 @@ impl Renderable for Entity {
                 gfx.draw_image(image.borrow(), region);
             }
             EntityView::Color(color) => gfx.fill_rect(&self.rect, color),
+            EntityView::Animation(animation) => {
+                let region = Rectangle::new(self.rect.pos, self.rect.size());
+                gfx.draw_image(animation.current_frame(), region);
+            }
         }
     }
 }
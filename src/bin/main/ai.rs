@@ -0,0 +1,307 @@
+use crate::Game;
+use quicksilver::geom::{Shape, Vector};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Side length, in world pixels, of a single pathfinding grid cell.
+pub const CELL_SIZE: f32 = 20.0;
+pub const GRID_WIDTH: i32 = 40; // 800.0 / CELL_SIZE
+pub const GRID_HEIGHT: i32 = 30; // 600.0 / CELL_SIZE
+
+pub type Cell = (i32, i32);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AiGoal {
+    Idle,
+    Seek(Vector),
+    Evade(Vector),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AiMovement {
+    Idle,
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+pub trait AI {
+    fn plan(&mut self, game: &Game, position: Vector) -> AiGoal;
+    fn step(&mut self, goal: &AiGoal) -> AiMovement;
+}
+
+pub fn world_to_cell(pos: Vector) -> Cell {
+    (
+        (pos.x / CELL_SIZE).floor() as i32,
+        (pos.y / CELL_SIZE).floor() as i32,
+    )
+}
+
+pub fn cell_to_world(cell: Cell) -> Vector {
+    Vector::new(
+        cell.0 as f32 * CELL_SIZE + CELL_SIZE / 2.0,
+        cell.1 as f32 * CELL_SIZE + CELL_SIZE / 2.0,
+    )
+}
+
+fn manhattan(a: Cell, b: Cell) -> i32 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+fn neighbors(cell: Cell) -> Vec<Cell> {
+    let (x, y) = cell;
+    [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+        .iter()
+        .filter(|&&(nx, ny)| nx >= 0 && nx < GRID_WIDTH && ny >= 0 && ny < GRID_HEIGHT)
+        .cloned()
+        .collect()
+}
+
+#[derive(PartialEq, Eq)]
+struct OpenEntry {
+    f: i32,
+    cell: Cell,
+}
+
+impl Ord for OpenEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.f.cmp(&self.f) // reverse so BinaryHeap pops the smallest f first
+    }
+}
+
+impl PartialOrd for OpenEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A* over the coarse grid, 4-connected, skipping `occupied` cells.
+/// Returns the cells from (but excluding) `start` up to and including `goal`.
+pub fn astar(start: Cell, goal: Cell, occupied: &HashSet<Cell>) -> Option<Vec<Cell>> {
+    if start == goal {
+        return Some(vec![]);
+    }
+
+    let mut open = BinaryHeap::new();
+    open.push(OpenEntry {
+        f: manhattan(start, goal),
+        cell: start,
+    });
+
+    let mut came_from: HashMap<Cell, Cell> = HashMap::new();
+    let mut g_score: HashMap<Cell, i32> = HashMap::new();
+    g_score.insert(start, 0);
+
+    while let Some(OpenEntry { cell, .. }) = open.pop() {
+        if cell == goal {
+            let mut path = vec![cell];
+            let mut current = cell;
+            while let Some(&prev) = came_from.get(&current) {
+                path.push(prev);
+                current = prev;
+            }
+            path.reverse();
+            path.remove(0);
+            return Some(path);
+        }
+
+        let current_g = g_score[&cell];
+        for next in neighbors(cell) {
+            if next != goal && occupied.contains(&next) {
+                continue;
+            }
+            let tentative_g = current_g + 1;
+            if tentative_g < *g_score.get(&next).unwrap_or(&i32::MAX) {
+                came_from.insert(next, cell);
+                g_score.insert(next, tentative_g);
+                open.push(OpenEntry {
+                    f: tentative_g + manhattan(next, goal),
+                    cell: next,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+fn movement_toward(from: Vector, to: Vector) -> AiMovement {
+    let dx = to.x - from.x;
+    let dy = to.y - from.y;
+    if dx.abs() > dy.abs() {
+        if dx > 0.0 {
+            AiMovement::Right
+        } else {
+            AiMovement::Left
+        }
+    } else if dy != 0.0 {
+        if dy > 0.0 {
+            AiMovement::Down
+        } else {
+            AiMovement::Up
+        }
+    } else {
+        AiMovement::Idle
+    }
+}
+
+fn nearest_threat(game: &Game, position: Vector) -> Option<Vector> {
+    game.bullets
+        .iter()
+        .map(|bullet| bullet.center())
+        .filter(|bullet| bullet.y > position.y && (bullet.x - position.x).abs() < CELL_SIZE * 2.0)
+        .min_by(|a, b| {
+            (a.y - position.y)
+                .abs()
+                .partial_cmp(&(b.y - position.y).abs())
+                .unwrap()
+        })
+}
+
+/// Shared A*-path-following state for `AI` impls that descend toward a goal
+/// cell; `BasicAi` and `DiverAi` differ only in `plan`, so the actual
+/// path-chasing lives here once instead of being duplicated per pattern.
+struct PathFollower {
+    position: Vector,
+    occupied: HashSet<Cell>,
+    path: Vec<Cell>,
+    path_goal: Option<Cell>,
+}
+
+impl PathFollower {
+    fn new() -> Self {
+        Self {
+            position: Vector::ZERO,
+            occupied: HashSet::new(),
+            path: vec![],
+            path_goal: None,
+        }
+    }
+
+    fn update_occupied(&mut self, game: &Game, position: Vector) {
+        self.position = position;
+
+        let own_cell = world_to_cell(position);
+        self.occupied = game
+            .enemies
+            .iter()
+            .map(|enemy| world_to_cell(enemy.entity.center()))
+            .filter(|cell| *cell != own_cell)
+            .collect();
+    }
+
+    fn step_toward(&mut self, goal: &AiGoal) -> AiMovement {
+        let target = match goal {
+            AiGoal::Idle => return AiMovement::Idle,
+            AiGoal::Seek(target) | AiGoal::Evade(target) => *target,
+        };
+
+        let start = world_to_cell(self.position);
+        let goal_cell = world_to_cell(target);
+
+        if start == goal_cell {
+            return AiMovement::Idle;
+        }
+
+        if self.path.is_empty() || self.path_goal != Some(goal_cell) {
+            self.path_goal = Some(goal_cell);
+            self.path = astar(start, goal_cell, &self.occupied).unwrap_or_default();
+        }
+
+        // `enemy_move_amount` is far smaller than `CELL_SIZE`, so a single
+        // cell takes several ticks to cross. Only advance past it once we've
+        // actually arrived, instead of draining the path once per tick.
+        if self.path.first() == Some(&start) {
+            self.path.remove(0);
+        }
+
+        match self.path.first().copied() {
+            None => AiMovement::Down,
+            Some(next) => movement_toward(self.position, cell_to_world(next)),
+        }
+    }
+}
+
+/// `EnemySpawn::pattern == 0`: descend toward the player's column, dodging
+/// sideways when a bullet is rising close beneath it.
+pub struct BasicAi {
+    follower: PathFollower,
+}
+
+impl BasicAi {
+    pub fn new() -> Self {
+        Self {
+            follower: PathFollower::new(),
+        }
+    }
+}
+
+impl AI for BasicAi {
+    fn plan(&mut self, game: &Game, position: Vector) -> AiGoal {
+        self.follower.update_occupied(game, position);
+
+        if let Some(threat) = nearest_threat(game, position) {
+            // Step a fixed two cells away so a bullet straight below still
+            // yields a target outside our own cell (which would mean Idle).
+            let away_dir = if threat.x > position.x { -1.0 } else { 1.0 };
+            let away_x = position.x + away_dir * CELL_SIZE * 2.0;
+            return AiGoal::Evade(Vector::new(away_x, position.y));
+        }
+
+        AiGoal::Seek(Vector::new(game.player.center().x, position.y + CELL_SIZE))
+    }
+
+    fn step(&mut self, goal: &AiGoal) -> AiMovement {
+        self.follower.step_toward(goal)
+    }
+}
+
+/// `EnemySpawn::pattern == 1`: beelines straight for the player's column and
+/// never dodges, unlike `BasicAi`.
+pub struct DiverAi {
+    follower: PathFollower,
+}
+
+impl DiverAi {
+    pub fn new() -> Self {
+        Self {
+            follower: PathFollower::new(),
+        }
+    }
+}
+
+impl AI for DiverAi {
+    fn plan(&mut self, game: &Game, position: Vector) -> AiGoal {
+        self.follower.update_occupied(game, position);
+        AiGoal::Seek(Vector::new(game.player.center().x, position.y + CELL_SIZE))
+    }
+
+    fn step(&mut self, goal: &AiGoal) -> AiMovement {
+        self.follower.step_toward(goal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn astar_finds_a_direct_path() {
+        let path = astar((0, 0), (2, 0), &HashSet::new());
+        assert_eq!(path, Some(vec![(1, 0), (2, 0)]));
+    }
+
+    #[test]
+    fn astar_returns_empty_path_when_already_at_goal() {
+        let path = astar((3, 3), (3, 3), &HashSet::new());
+        assert_eq!(path, Some(vec![]));
+    }
+
+    #[test]
+    fn astar_returns_none_when_goal_is_boxed_in() {
+        let occupied: HashSet<Cell> = [(4, 5), (6, 5), (5, 4), (5, 6)].into_iter().collect();
+        let path = astar((0, 0), (5, 5), &occupied);
+        assert_eq!(path, None);
+    }
+}
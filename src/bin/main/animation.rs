@@ -0,0 +1,84 @@
+use quicksilver::graphics::Image;
+use std::borrow::Borrow;
+
+/// A sequence of frames advanced one step per logic tick (see `Entity::tick_animation`,
+/// driven from `Game::update`, not the draw loop).
+#[derive(Clone)]
+pub struct SpriteAnimation {
+    frames: Vec<Box<Image>>,
+    frame_duration: u32,
+    looping: bool,
+    current_frame: usize,
+    ticks_in_frame: u32,
+    finished: bool,
+}
+
+impl SpriteAnimation {
+    pub fn new(frames: Vec<Box<Image>>, frame_duration: u32, looping: bool) -> Self {
+        Self {
+            frames,
+            frame_duration,
+            looping,
+            current_frame: 0,
+            ticks_in_frame: 0,
+            finished: false,
+        }
+    }
+
+    pub fn tick(&mut self) {
+        if self.finished {
+            return;
+        }
+
+        self.ticks_in_frame += 1;
+        if self.ticks_in_frame < self.frame_duration {
+            return;
+        }
+        self.ticks_in_frame = 0;
+
+        let (next_frame, finished) = Self::advance_frame(self.current_frame, self.frames.len(), self.looping);
+        self.current_frame = next_frame;
+        self.finished = finished;
+    }
+
+    /// The frame-index/loop-vs-finish transition, factored out of `tick` so
+    /// it can be tested without a real `Image`.
+    fn advance_frame(current: usize, frame_count: usize, looping: bool) -> (usize, bool) {
+        let next = current + 1;
+        if next < frame_count {
+            (next, false)
+        } else if looping {
+            (0, false)
+        } else {
+            (frame_count - 1, true)
+        }
+    }
+
+    pub fn current_frame(&self) -> &Image {
+        self.frames[self.current_frame].borrow()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advance_frame_steps_forward_within_bounds() {
+        assert_eq!(SpriteAnimation::advance_frame(0, 3, true), (1, false));
+    }
+
+    #[test]
+    fn advance_frame_loops_back_to_the_first_frame() {
+        assert_eq!(SpriteAnimation::advance_frame(2, 3, true), (0, false));
+    }
+
+    #[test]
+    fn advance_frame_holds_the_last_frame_and_finishes_when_not_looping() {
+        assert_eq!(SpriteAnimation::advance_frame(2, 3, false), (2, true));
+    }
+}
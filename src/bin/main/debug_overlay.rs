@@ -0,0 +1,108 @@
+use crate::Game;
+use quicksilver::{
+    geom::Vector,
+    graphics::{Color, FontRenderer, Graphics},
+};
+use space_invaders::utils::timer::Timer;
+use std::cell::{Cell, RefCell};
+
+/// Toggled with F1; draws over the normal render path without touching it.
+/// Shows live entity bounds/counts and FPS, and lets `Game`'s tunable
+/// movement/fire/spawn constants (see `Key::Equals`/`Minus`/`LBracket`/
+/// `RBracket`/`Comma`/`Period`/`Slash`/`Backslash` in `Game::handle_key`) be
+/// adjusted without a recompile.
+pub struct DebugOverlay {
+    enabled: Cell<bool>,
+    frame_count: Cell<u32>,
+    fps: Cell<u32>,
+    fps_timer: RefCell<Timer>,
+}
+
+impl DebugOverlay {
+    pub fn new() -> Self {
+        Self {
+            enabled: Cell::new(false),
+            frame_count: Cell::new(0),
+            fps: Cell::new(0),
+            fps_timer: RefCell::new(Timer::time_per_second(1.0)),
+        }
+    }
+
+    pub fn toggle(&self) {
+        self.enabled.set(!self.enabled.get());
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.get()
+    }
+
+    pub fn render(&self, game: &Game, gfx: &mut Graphics, font: Option<&mut FontRenderer>) {
+        self.frame_count.set(self.frame_count.get() + 1);
+        if self.fps_timer.borrow_mut().tick() {
+            self.fps.set(self.frame_count.get());
+            self.frame_count.set(0);
+        }
+
+        if !self.enabled.get() {
+            return;
+        }
+
+        for enemy in &game.enemies {
+            gfx.stroke_rect(&enemy.bounds(), Color::YELLOW);
+        }
+        gfx.stroke_rect(&game.player.bounds(), Color::YELLOW);
+        for bullet in &game.bullets {
+            gfx.stroke_rect(&bullet.bounds(), Color::YELLOW);
+        }
+
+        // No font loaded (see `load_font`'s graceful fallback): bounds above
+        // still draw, but the text readouts below need a renderer.
+        let font = match font {
+            Some(font) => font,
+            None => return,
+        };
+
+        self.draw_reading(
+            gfx,
+            font,
+            0,
+            &format!("Enemies: {}", game.enemies.len()),
+            Color::CYAN,
+        );
+        self.draw_reading(
+            gfx,
+            font,
+            1,
+            &format!("Bullets: {}", game.bullets.len()),
+            Color::MAGENTA,
+        );
+        self.draw_reading(gfx, font, 2, &format!("FPS: {}", self.fps.get()), Color::GREEN);
+        self.draw_reading(
+            gfx,
+            font,
+            3,
+            &format!("Player X: {:.0}", game.player.center().x),
+            Color::WHITE,
+        );
+        self.draw_reading(
+            gfx,
+            font,
+            4,
+            &format!("Spawn copies: x{}", game.extra_spawn_count + 1),
+            Color::YELLOW,
+        );
+    }
+
+    fn draw_reading(
+        &self,
+        gfx: &mut Graphics,
+        font: &mut FontRenderer,
+        row: u32,
+        text: &str,
+        color: Color,
+    ) {
+        let y = 450.0 + row as f32 * 16.0;
+        font.draw(gfx, text, color, Vector::new(10.0, y))
+            .expect("failed to draw debug overlay text");
+    }
+}
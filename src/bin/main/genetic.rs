@@ -0,0 +1,354 @@
+use crate::ai::{AiGoal, AiMovement, AI};
+use crate::scenes::{Scene, SceneTransition};
+use crate::{Entity, EntityView, Game, Renderable, SharedFont, ENEMY_BREACH_LINE};
+use quicksilver::{
+    geom::Vector,
+    graphics::{Color, Graphics, Image},
+    lifecycle::{event::KeyboardEvent, Key},
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+use crate::waves::WaveConfig;
+
+const INPUT_SIZE: usize = 4;
+const HIDDEN_SIZE: usize = 6;
+const OUTPUT_SIZE: usize = 5; // left, right, down, hold, dodge
+const GENOME_LEN: usize = INPUT_SIZE * HIDDEN_SIZE + HIDDEN_SIZE * OUTPUT_SIZE;
+
+const TOURNAMENT_SIZE: usize = 3;
+const MUTATION_RATE: f32 = 0.05;
+const MUTATION_STRENGTH: f32 = 0.2;
+
+pub const GENOME_SAVE_PATH: &str = "best_genome.json";
+
+/// A fixed-topology feedforward network's flattened weights. Crossover relies
+/// on every genome in a population sharing this exact layout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Genome {
+    weights: Vec<f32>,
+}
+
+impl Genome {
+    pub fn random() -> Self {
+        let mut rng = rand::thread_rng();
+        Self {
+            weights: (0..GENOME_LEN).map(|_| rng.gen_range(-1.0..1.0)).collect(),
+        }
+    }
+
+    fn feed_forward(&self, inputs: [f32; INPUT_SIZE]) -> [f32; OUTPUT_SIZE] {
+        let (w1, w2) = self.weights.split_at(INPUT_SIZE * HIDDEN_SIZE);
+
+        let mut hidden = [0.0f32; HIDDEN_SIZE];
+        for (h, slot) in hidden.iter_mut().enumerate() {
+            let sum: f32 = (0..INPUT_SIZE)
+                .map(|i| inputs[i] * w1[h * INPUT_SIZE + i])
+                .sum();
+            *slot = sum.tanh();
+        }
+
+        let mut output = [0.0f32; OUTPUT_SIZE];
+        for (o, slot) in output.iter_mut().enumerate() {
+            *slot = (0..HIDDEN_SIZE).map(|h| hidden[h] * w2[o * HIDDEN_SIZE + h]).sum();
+        }
+        output
+    }
+}
+
+pub fn save_best(genome: &Genome, path: &str) {
+    let json = serde_json::to_string(genome).expect("failed to serialize genome");
+    std::fs::write(path, json).expect("failed to write genome file");
+}
+
+pub fn load_best(path: &str) -> Option<Genome> {
+    let data = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+/// A pool of genomes evolved across generations via tournament selection,
+/// single-point crossover, and Gaussian mutation.
+pub struct Population {
+    pub genomes: Vec<Genome>,
+    pub generation: u32,
+}
+
+impl Population {
+    pub fn new(size: usize) -> Self {
+        Self {
+            genomes: (0..size).map(|_| Genome::random()).collect(),
+            generation: 0,
+        }
+    }
+
+    /// `fitness` must be parallel to `self.genomes`.
+    pub fn evolve(&mut self, fitness: &[f32]) {
+        let next = (0..self.genomes.len())
+            .map(|_| {
+                let parent_a = tournament_select(&self.genomes, fitness);
+                let parent_b = tournament_select(&self.genomes, fitness);
+                let mut child = crossover(parent_a, parent_b);
+                mutate(&mut child);
+                child
+            })
+            .collect();
+
+        self.genomes = next;
+        self.generation += 1;
+    }
+}
+
+fn tournament_select<'a>(genomes: &'a [Genome], fitness: &[f32]) -> &'a Genome {
+    let mut rng = rand::thread_rng();
+    let mut best = rng.gen_range(0..genomes.len());
+    for _ in 1..TOURNAMENT_SIZE {
+        let candidate = rng.gen_range(0..genomes.len());
+        if fitness[candidate] > fitness[best] {
+            best = candidate;
+        }
+    }
+    &genomes[best]
+}
+
+fn crossover(a: &Genome, b: &Genome) -> Genome {
+    let point = rand::thread_rng().gen_range(0..GENOME_LEN);
+    let weights = a.weights[..point]
+        .iter()
+        .chain(b.weights[point..].iter())
+        .copied()
+        .collect();
+    Genome { weights }
+}
+
+fn mutate(genome: &mut Genome) {
+    let mut rng = rand::thread_rng();
+    for weight in &mut genome.weights {
+        if rng.gen::<f32>() < MUTATION_RATE {
+            *weight += gaussian(&mut rng) * MUTATION_STRENGTH;
+        }
+    }
+}
+
+fn gaussian(rng: &mut impl Rng) -> f32 {
+    let u1: f32 = rng.gen_range(f32::EPSILON..1.0);
+    let u2: f32 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+fn distance(a: Vector, b: Vector) -> f32 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+}
+
+/// Drives an enemy directly from a `Genome`'s network output instead of a
+/// goal/pathfinding loop; `plan` runs the network and `step` just reports
+/// the resulting movement, so `goal` itself carries no information here.
+pub struct GeneticAi {
+    genome: Genome,
+    pending: AiMovement,
+}
+
+impl GeneticAi {
+    pub fn new(genome: Genome) -> Self {
+        Self {
+            genome,
+            pending: AiMovement::Idle,
+        }
+    }
+}
+
+impl AI for GeneticAi {
+    fn plan(&mut self, game: &Game, position: Vector) -> AiGoal {
+        let nearest_bullet = game
+            .bullets
+            .iter()
+            .map(|bullet| distance(bullet.center(), position))
+            .fold(f32::MAX, f32::min);
+
+        let inputs = [
+            (game.player.center().x - position.x) / 800.0,
+            (nearest_bullet / 600.0).min(1.0),
+            position.x / 800.0,
+            position.y / 600.0,
+        ];
+
+        let output = self.genome.feed_forward(inputs);
+        let action = output[..4]
+            .iter()
+            .enumerate()
+            .fold((0usize, f32::MIN), |best, (i, &v)| {
+                if v > best.1 {
+                    (i, v)
+                } else {
+                    best
+                }
+            })
+            .0;
+
+        self.pending = match action {
+            0 => AiMovement::Left,
+            1 => AiMovement::Right,
+            2 => AiMovement::Down,
+            _ => AiMovement::Idle,
+        };
+
+        let wants_dodge = output[4] > 0.0;
+        if wants_dodge {
+            self.pending = match self.pending {
+                AiMovement::Left => AiMovement::Right,
+                AiMovement::Right => AiMovement::Left,
+                other => other,
+            };
+        }
+
+        AiGoal::Idle
+    }
+
+    fn step(&mut self, _goal: &AiGoal) -> AiMovement {
+        self.pending
+    }
+}
+
+const POPULATION_SIZE: usize = 20;
+const TICKS_PER_FRAME: u32 = 20;
+
+/// Hard cap on an episode's length, so a genome that never breaches the
+/// line or clears every wave (e.g. one that learns to sit still) can't
+/// freeze training forever.
+const MAX_EPISODE_TICKS: u32 = 1800;
+
+/// How often a training episode fires a bullet up the player's column,
+/// standing in for the player so `GeneticAi` actually has something to
+/// dodge; without it `bullets_evaded` is always zero and fitness rewards
+/// stalling instead of survival skill.
+const BULLET_FIRE_INTERVAL: u32 = 15;
+
+/// Runs each genome as a full game episode (fitness = ticks survived plus
+/// bullets evaded), then evolves the population and starts the next
+/// episode. Pushed over the title screen; Esc returns there.
+pub struct TrainingScene {
+    images: HashMap<String, Box<Image>>,
+    wave_config: WaveConfig,
+    font: Option<SharedFont>,
+    population: Population,
+    current_genome: usize,
+    fitness: Vec<f32>,
+    episode: Game,
+    episode_ticks: u32,
+}
+
+impl TrainingScene {
+    pub fn new(images: HashMap<String, Box<Image>>, wave_config: WaveConfig, font: Option<SharedFont>) -> Self {
+        let population = Population::new(POPULATION_SIZE);
+        let episode = Self::spawn_episode(&images, &wave_config, &font, &population.genomes[0]);
+        let fitness = vec![0.0; population.genomes.len()];
+
+        Self {
+            images,
+            wave_config,
+            font,
+            population,
+            current_genome: 0,
+            fitness,
+            episode,
+            episode_ticks: 0,
+        }
+    }
+
+    fn spawn_episode(
+        images: &HashMap<String, Box<Image>>,
+        wave_config: &WaveConfig,
+        font: &Option<SharedFont>,
+        genome: &Genome,
+    ) -> Game {
+        let mut game = Game::new(images.clone(), wave_config.clone(), font.clone());
+        // Later waves are spawned from `trained_genome`, so point it at the
+        // genome under evaluation too.
+        game.trained_genome = Some(genome.clone());
+        for enemy in &mut game.enemies {
+            enemy.ai = Some(Box::new(GeneticAi::new(genome.clone())));
+        }
+        game
+    }
+
+    fn episode_over(&self) -> bool {
+        let no_more_waves = self.episode.current_wave + 1 >= self.episode.wave_config.waves.len();
+        let enemies_cleared = self.episode.enemies.is_empty() && no_more_waves;
+        let enemies_breached = self
+            .episode
+            .enemies
+            .iter()
+            .any(|enemy| enemy.entity.center().y > ENEMY_BREACH_LINE);
+        let timed_out = self.episode_ticks >= MAX_EPISODE_TICKS;
+
+        enemies_cleared || enemies_breached || timed_out
+    }
+
+    /// Stands in for the player firing, so episodes exercise dodging instead
+    /// of running risk-free.
+    fn fire_training_bullet(&mut self) {
+        let Vector { x, y } = self.episode.player.center();
+        self.episode
+            .bullets
+            .push(Entity::new_bullet(x, y, EntityView::Color(Color::GREEN)));
+    }
+
+    fn finish_episode(&mut self) {
+        let fitness = self.episode_ticks as f32 + self.episode.bullets_evaded as f32;
+        self.fitness[self.current_genome] = fitness;
+        self.current_genome += 1;
+
+        if self.current_genome >= self.population.genomes.len() {
+            let best = self
+                .fitness
+                .iter()
+                .enumerate()
+                .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+                .map(|(i, _)| i)
+                .unwrap_or(0);
+            save_best(&self.population.genomes[best], GENOME_SAVE_PATH);
+
+            self.population.evolve(&self.fitness);
+            self.fitness = vec![0.0; self.population.genomes.len()];
+            self.current_genome = 0;
+        }
+
+        let genome = self.population.genomes[self.current_genome].clone();
+        self.episode = Self::spawn_episode(&self.images, &self.wave_config, &self.font, &genome);
+        self.episode_ticks = 0;
+    }
+}
+
+impl Scene for TrainingScene {
+    fn update(&mut self) -> SceneTransition {
+        for _ in 0..TICKS_PER_FRAME {
+            if self.episode_over() {
+                break;
+            }
+            if self.episode_ticks % BULLET_FIRE_INTERVAL == 0 {
+                self.fire_training_bullet();
+            }
+            self.episode.update();
+            self.episode_ticks += 1;
+        }
+
+        if self.episode_over() {
+            self.finish_episode();
+        }
+
+        SceneTransition::None
+    }
+
+    fn render(&self, gfx: &mut Graphics) {
+        Renderable::render(&self.episode, gfx);
+    }
+
+    fn handle_key(&mut self, event: KeyboardEvent) -> SceneTransition {
+        if event.is_down() && event.key() == Key::Escape {
+            SceneTransition::Pop
+        } else {
+            SceneTransition::None
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use quicksilver::geom::{Rectangle, Shape, Vector};
+use quicksilver::graphics::{Color, FontRenderer, Graphics};
+
+/// Which playback speed the control bar has selected.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Playback {
+    Paused,
+    Normal,
+    FastForward,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ControlBarButton {
+    Pause,
+    Play,
+    FastForward,
+    Restart,
+}
+
+/// A row of clickable buttons along the bottom of the field, mirroring a
+/// simulation-style control strip (pause / play / fast-forward / restart).
+pub struct ControlBar {
+    pause: Rectangle,
+    play: Rectangle,
+    fast_forward: Rectangle,
+    restart: Rectangle,
+}
+
+impl ControlBar {
+    pub fn new() -> Self {
+        let y = 570.0;
+        let size = Vector::new(40.0, 24.0);
+        Self {
+            pause: Rectangle::new(Vector::new(600.0, y), size),
+            play: Rectangle::new(Vector::new(650.0, y), size),
+            fast_forward: Rectangle::new(Vector::new(700.0, y), size),
+            restart: Rectangle::new(Vector::new(750.0, y), size),
+        }
+    }
+
+    pub fn hit_test(&self, position: Vector) -> Option<ControlBarButton> {
+        if self.pause.contains(position) {
+            Some(ControlBarButton::Pause)
+        } else if self.play.contains(position) {
+            Some(ControlBarButton::Play)
+        } else if self.fast_forward.contains(position) {
+            Some(ControlBarButton::FastForward)
+        } else if self.restart.contains(position) {
+            Some(ControlBarButton::Restart)
+        } else {
+            None
+        }
+    }
+
+    pub fn render(&self, gfx: &mut Graphics) {
+        gfx.fill_rect(&self.pause, Color::WHITE);
+        gfx.fill_rect(&self.play, Color::GREEN);
+        gfx.fill_rect(&self.fast_forward, Color::YELLOW);
+        gfx.fill_rect(&self.restart, Color::RED);
+    }
+}
+
+/// Score and remaining lives readout, drawn above the control bar.
+pub struct Hud;
+
+impl Hud {
+    /// No-ops if `font` is `None`, i.e. `font.ttf` failed to load (see
+    /// `load_font`'s graceful fallback) — the HUD is pure text, so there's
+    /// nothing else for it to draw.
+    pub fn render(&self, gfx: &mut Graphics, font: Option<&mut FontRenderer>, score: u32, lives: u32) {
+        let font = match font {
+            Some(font) => font,
+            None => return,
+        };
+        font.draw(gfx, &format!("Score: {}", score), Color::CYAN, Vector::new(10.0, 20.0))
+            .expect("failed to draw HUD text");
+        font.draw(gfx, &format!("Lives: {}", lives), Color::RED, Vector::new(10.0, 40.0))
+            .expect("failed to draw HUD text");
+    }
+}
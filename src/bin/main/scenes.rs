@@ -0,0 +1,222 @@
+use crate::waves::WaveConfig;
+use crate::SharedFont;
+use quicksilver::{
+    geom::{Rectangle, Vector},
+    graphics::{Color, Graphics, Image},
+    lifecycle::event::KeyboardEvent,
+    lifecycle::Key,
+};
+use std::collections::HashMap;
+
+/// What a `Scene` wants the `SceneManager` to do in response to an update, a key press or a click.
+pub enum SceneTransition {
+    None,
+    Push(Box<dyn Scene>),
+    Pop,
+    Replace(Box<dyn Scene>),
+    /// Clears the whole stack and starts fresh from `scene`. Use this instead
+    /// of `Replace` when unwinding scenes pushed on top of the one being
+    /// replaced (e.g. restarting from a `GameOverScene` pushed over `Game`),
+    /// since `Replace` only swaps the top of the stack.
+    Root(Box<dyn Scene>),
+}
+
+pub trait Scene {
+    fn update(&mut self) -> SceneTransition {
+        SceneTransition::None
+    }
+    fn render(&self, gfx: &mut Graphics);
+    fn handle_key(&mut self, event: KeyboardEvent) -> SceneTransition {
+        let _ = event;
+        SceneTransition::None
+    }
+    fn handle_click(&mut self, position: Vector) -> SceneTransition {
+        let _ = position;
+        SceneTransition::None
+    }
+
+    /// Drawn for scenes sitting underneath the active one, e.g. the game
+    /// behind a `PauseScene`. Defaults to a plain render plus a translucent
+    /// overlay; override for anything fancier.
+    fn render_dimmed(&self, gfx: &mut Graphics) {
+        self.render(gfx);
+        gfx.fill_rect(
+            &Rectangle::new(Vector::ZERO, Vector::new(800.0, 600.0)),
+            Color {
+                r: 0.0,
+                g: 0.0,
+                b: 0.0,
+                a: 0.5,
+            },
+        );
+    }
+}
+
+/// A stack of scenes. Only the top scene updates and receives key events;
+/// scenes beneath it are still drawn (dimmed) so menus can overlay gameplay.
+pub struct SceneManager {
+    stack: Vec<Box<dyn Scene>>,
+}
+
+impl SceneManager {
+    pub fn new(initial: Box<dyn Scene>) -> Self {
+        Self {
+            stack: vec![initial],
+        }
+    }
+
+    pub fn update(&mut self) {
+        if let Some(top) = self.stack.last_mut() {
+            let transition = top.update();
+            self.apply(transition);
+        }
+    }
+
+    pub fn handle_key(&mut self, event: KeyboardEvent) {
+        if let Some(top) = self.stack.last_mut() {
+            let transition = top.handle_key(event);
+            self.apply(transition);
+        }
+    }
+
+    pub fn handle_click(&mut self, position: Vector) {
+        if let Some(top) = self.stack.last_mut() {
+            let transition = top.handle_click(position);
+            self.apply(transition);
+        }
+    }
+
+    pub fn render(&self, gfx: &mut Graphics) {
+        let top = self.stack.len().saturating_sub(1);
+        for (i, scene) in self.stack.iter().enumerate() {
+            if i == top {
+                scene.render(gfx);
+            } else {
+                scene.render_dimmed(gfx);
+            }
+        }
+    }
+
+    fn apply(&mut self, transition: SceneTransition) {
+        match transition {
+            SceneTransition::None => (),
+            SceneTransition::Push(scene) => self.stack.push(scene),
+            SceneTransition::Pop => {
+                self.stack.pop();
+            }
+            SceneTransition::Replace(scene) => {
+                self.stack.pop();
+                self.stack.push(scene);
+            }
+            SceneTransition::Root(scene) => {
+                self.stack.clear();
+                self.stack.push(scene);
+            }
+        }
+    }
+}
+
+/// The title screen shown on launch; presses Enter to start a fresh game.
+pub struct TitleScene {
+    images: HashMap<String, Box<Image>>,
+    wave_config: WaveConfig,
+    font: Option<SharedFont>,
+}
+
+impl TitleScene {
+    pub fn new(images: HashMap<String, Box<Image>>, wave_config: WaveConfig, font: Option<SharedFont>) -> Self {
+        Self {
+            images,
+            wave_config,
+            font,
+        }
+    }
+}
+
+impl Scene for TitleScene {
+    fn render(&self, gfx: &mut Graphics) {
+        gfx.fill_rect(
+            &Rectangle::new(Vector::ZERO, Vector::new(800.0, 600.0)),
+            Color::BLACK,
+        );
+    }
+
+    fn handle_key(&mut self, event: KeyboardEvent) -> SceneTransition {
+        if !event.is_down() {
+            return SceneTransition::None;
+        }
+
+        match event.key() {
+            Key::Return => SceneTransition::Replace(Box::new(crate::Game::new(
+                self.images.clone(),
+                self.wave_config.clone(),
+                self.font.clone(),
+            ))),
+            // Starts the genetic-algorithm training loop instead of normal play.
+            Key::T => SceneTransition::Push(Box::new(crate::genetic::TrainingScene::new(
+                self.images.clone(),
+                self.wave_config.clone(),
+                self.font.clone(),
+            ))),
+            _ => SceneTransition::None,
+        }
+    }
+}
+
+/// Pushed over the running `Game` when Esc is pressed; popped by pressing Esc again.
+pub struct PauseScene;
+
+impl Scene for PauseScene {
+    fn render(&self, _gfx: &mut Graphics) {
+        // the paused Game scene shows through, dimmed, underneath this one
+    }
+
+    fn handle_key(&mut self, event: KeyboardEvent) -> SceneTransition {
+        if event.is_down() && event.key() == Key::Escape {
+            SceneTransition::Pop
+        } else {
+            SceneTransition::None
+        }
+    }
+}
+
+/// Shown once the player's last life is lost or every wave has been cleared;
+/// Enter starts over.
+pub struct GameOverScene {
+    images: HashMap<String, Box<Image>>,
+    wave_config: WaveConfig,
+    font: Option<SharedFont>,
+}
+
+impl GameOverScene {
+    pub fn new(images: HashMap<String, Box<Image>>, wave_config: WaveConfig, font: Option<SharedFont>) -> Self {
+        Self {
+            images,
+            wave_config,
+            font,
+        }
+    }
+}
+
+impl Scene for GameOverScene {
+    fn render(&self, gfx: &mut Graphics) {
+        gfx.fill_rect(
+            &Rectangle::new(Vector::ZERO, Vector::new(800.0, 600.0)),
+            Color::RED,
+        );
+    }
+
+    fn handle_key(&mut self, event: KeyboardEvent) -> SceneTransition {
+        if event.is_down() && event.key() == Key::Return {
+            // GameOverScene is pushed on top of the dead Game, so unwind the
+            // whole stack instead of `Replace`-ing just the top of it.
+            SceneTransition::Root(Box::new(crate::Game::new(
+                self.images.clone(),
+                self.wave_config.clone(),
+                self.font.clone(),
+            )))
+        } else {
+            SceneTransition::None
+        }
+    }
+}
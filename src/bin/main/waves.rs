@@ -0,0 +1,58 @@
+use serde::Deserialize;
+
+/// A single enemy placement within a `Wave`, as authored in the wave config file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnemySpawn {
+    pub x: f32,
+    pub y: f32,
+    pub sprite: String,
+    pub hp: u32,
+    /// Selects a movement pattern: `0` for `BasicAi` (dodges bullets), `1`
+    /// for `DiverAi` (beelines for the player, no dodging). Any other value
+    /// falls back to `BasicAi`. Ignored while a trained genome is loaded:
+    /// every enemy then uses `GeneticAi`.
+    pub pattern: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Wave {
+    pub enemies: Vec<EnemySpawn>,
+    /// Seconds to wait after this wave is cleared before the next one spawns.
+    pub delay: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WaveConfig {
+    pub waves: Vec<Wave>,
+}
+
+impl WaveConfig {
+    /// Loads waves from `path`, falling back to `default_waves` if the file
+    /// is missing or fails to parse, so a bad asset degrades gracefully
+    /// instead of taking down the whole game at startup.
+    pub async fn load(path: &str) -> Self {
+        let bytes = match quicksilver::load_file(path).await {
+            Ok(bytes) => bytes,
+            Err(_) => return Self::default_waves(),
+        };
+
+        serde_json::from_slice(&bytes).unwrap_or_else(|_| Self::default_waves())
+    }
+
+    /// Mirrors the original hardcoded nine-enemy formation.
+    fn default_waves() -> Self {
+        let enemies = (1..10)
+            .map(|i| EnemySpawn {
+                x: 150.0 + i as f32 * 50.0,
+                y: 20.0,
+                sprite: "enemy".to_string(),
+                hp: 1,
+                pattern: 0,
+            })
+            .collect();
+
+        Self {
+            waves: vec![Wave { enemies, delay: 3.0 }],
+        }
+    }
+}